@@ -1,27 +1,119 @@
 use cargo::core::Package;
 use cargo::{ human, Config, CargoResult };
 
+use license::License;
 use licensed::Licensed;
+use options::{ CheckOptions, Policy };
 
-pub fn run(root: &Package, packages: Vec<Package>, config: &Config) -> CargoResult<()> {
+pub fn run(root: &Package, packages: Vec<Package>, config: &Config, options: &CheckOptions) -> CargoResult<usize> {
     let mut fail = 0;
+    let mut copyleft = Vec::new();
+    let mut permissive = false;
     let license = root.license();
 
+    let mut deny = options.deny.clone();
+    for denied in &options.deny {
+        if let License::Custom(ref s) = *denied {
+            warn(config, options, format!("Unrecognized license {} passed to --deny, it will only match that exact license string", s))?;
+        }
+        deny.extend(denied.or_later_counterpart());
+    }
+
+    // The count is of packages without a confidently resolved license, so
+    // unknown licenses count as failures too
+    let no_unknown = options.no_unknown || options.count;
+
     for package in packages {
-        let can_include = license.can_include(&package.license());
+        // The root package is part of the resolved set, but it doesn't need to
+        // pass its own policy
+        if package.package_id() == root.package_id() {
+            continue;
+        }
+
+        let package_license = package.license();
+
+        if package_license.is_strong_copyleft() {
+            copyleft.push(package.name().to_owned());
+        } else if package_license.is_permissive() {
+            permissive = true;
+        }
+
+        if !package_license.satisfies(&|license| match *license {
+            License::AGPL_3_0 | License::AGPL_3_0Plus => false,
+            _ => true,
+        }) {
+            warn(config, options, format!("Package {} is licensed under {}, its network use clause applies to software accessed over a network", package.name(), package_license))?;
+        }
+
+        if let License::Unspecified = package_license {
+            if options.allow_unspecified {
+                warn(config, options, format!("Package {} does not specify a license", package.name()))?;
+                continue;
+            }
+        }
+
+        if no_unknown && !package_license.satisfies(&|license| match *license {
+            License::Custom(_) | License::File(_) | License::Unspecified => false,
+            _ => true,
+        }) {
+            let declared = package.declared_license();
+            if let Some(raw) = declared.license.or(declared.license_file) {
+                error(config, options, format!("Cannot include package {}, license {:?} is not recognized", package.name(), raw))?;
+            } else {
+                error(config, options, format!("Cannot include package {}, it does not specify a license", package.name()))?;
+            }
+            fail += 1;
+            continue;
+        }
+
+        if !package_license.satisfies(&|license| !deny.contains(license)) {
+            error(config, options, format!("Cannot include package {}, license {} is denied", package.name(), package_license))?;
+            fail += 1;
+            continue;
+        }
+
+        if !options.allow.is_empty() && !package_license.satisfies(&|license| options.allow.contains(license)) {
+            error(config, options, format!("Cannot include package {}, license {} is not allowed", package.name(), package_license))?;
+            fail += 1;
+            continue;
+        }
+
+        let can_include = license.can_include(&package_license);
         if let Some(can_include) = can_include {
             if !can_include {
-                config.shell().error(format!("Cannot include package {}, license {} is incompatible with {}", package.name(), package.license(), license))?;
+                error(config, options, format!("Cannot include package {}, license {} is incompatible with {}", package.name(), package_license, license))?;
                 fail += 1;
             }
+        } else if options.count {
+            fail += 1;
         } else {
-            config.shell().warn(format!("Unknown whether package {} with license {} is compatible with {}", package.name(), package.license(), license))?;
+            warn(config, options, format!("Unknown whether package {} with license {} is compatible with {}", package.name(), package_license, license))?;
         }
     }
 
+    // A strong copyleft root is not a proprietary product
+    if options.policy == Policy::Proprietary && permissive && !copyleft.is_empty() && !license.is_strong_copyleft() {
+        warn(config, options, format!("Packages {} are under a strong copyleft license, mixing them into a proprietary product is likely not allowed", copyleft.join(", ")))?;
+    }
+
+    if options.count {
+        // Printed directly rather than through the shell so it isn't silenced
+        // by --quiet
+        println!("{}", fail);
+        return Ok(fail);
+    }
+
     if fail > 0 {
-        Err(human("Incompatible license"))
+        Err(human(format!("{} {} the license check", fail, if fail == 1 { "package failed" } else { "packages failed" })))
     } else {
-        Ok(())
+        Ok(0)
     }
 }
+
+fn error(config: &Config, options: &CheckOptions, message: String) -> CargoResult<()> {
+    if options.count { Ok(()) } else { config.shell().error(message) }
+}
+
+fn warn(config: &Config, options: &CheckOptions, message: String) -> CargoResult<()> {
+    if options.count { Ok(()) } else { config.shell().warn(message) }
+}
@@ -0,0 +1,20 @@
+//! The license resolution used by the `cargo lichking` subcommand, for use
+//! from other tooling.
+
+extern crate cargo;
+extern crate void;
+
+pub mod license;
+pub mod licensed;
+pub mod load;
+
+use cargo::core::Package;
+
+use license::License;
+use licensed::Licensed;
+
+pub fn licenses_for(packages: &[Package]) -> Vec<(Package, License)> {
+    packages.iter()
+        .map(|package| (package.clone(), package.license()))
+        .collect()
+}
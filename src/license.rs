@@ -1,13 +1,22 @@
 use std::fmt;
+use std::iter::Peekable;
 use std::str::FromStr;
 use std::path::PathBuf;
+use std::vec;
 use void::Void;
 
-#[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug)]
+#[derive(Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Debug)]
 #[allow(non_camel_case_types)]
 pub enum License {
     MIT,
     X11,
+    Unlicense,
+    ISC,
+    Zlib,
+    BSL_1_0,
+    WTFPL,
+    BSD_0,
+    BSD_2_Clause,
     BSD_3_Clause,
     Apache_2_0,
     LGPL_2_0,
@@ -17,6 +26,8 @@ pub enum License {
     LGPL_3_0Plus,
     MPL_1_1,
     MPL_2_0,
+    EUPL_1_2,
+    CDDL_1_0,
     GPL_2_0,
     GPL_2_0Plus,
     GPL_3_0,
@@ -26,6 +37,8 @@ pub enum License {
     Custom(String),
     File(PathBuf),
     Multiple(Vec<License>),
+    All(Vec<License>),
+    WithException(Box<License>, String),
     Unspecified,
 }
 
@@ -50,27 +63,86 @@ macro_rules! compatibility {
 }
 
 impl License {
+    /// Whether some choice of licenses allowed by this expression only
+    /// contains licenses accepted by `accept`.
+    pub fn satisfies<F: Fn(&License) -> bool>(&self, accept: &F) -> bool {
+        match *self {
+            License::Multiple(ref licenses) => licenses.iter().any(|license| license.satisfies(accept)),
+            License::All(ref licenses) => licenses.iter().all(|license| license.satisfies(accept)),
+            License::WithException(ref license, _) => license.satisfies(accept),
+            ref license => accept(license),
+        }
+    }
+
+    /// The other form of a license with an "or later" variant, e.g.
+    /// `GPL-3.0+` for `GPL-3.0` and `GPL-3.0` for `GPL-3.0+`.
+    pub fn or_later_counterpart(&self) -> Option<License> {
+        use self::License::*;
+        match *self {
+            LGPL_2_1     => Some(LGPL_2_1Plus),
+            LGPL_2_1Plus => Some(LGPL_2_1),
+            LGPL_3_0     => Some(LGPL_3_0Plus),
+            LGPL_3_0Plus => Some(LGPL_3_0),
+            GPL_2_0      => Some(GPL_2_0Plus),
+            GPL_2_0Plus  => Some(GPL_2_0),
+            GPL_3_0      => Some(GPL_3_0Plus),
+            GPL_3_0Plus  => Some(GPL_3_0),
+            AGPL_3_0     => Some(AGPL_3_0Plus),
+            AGPL_3_0Plus => Some(AGPL_3_0),
+            _ => None,
+        }
+    }
+
+    /// Whether this can only be used under a strong copyleft license.
+    pub fn is_strong_copyleft(&self) -> bool {
+        !self.satisfies(&|license| match *license {
+            License::GPL_2_0 | License::GPL_2_0Plus |
+            License::GPL_3_0 | License::GPL_3_0Plus |
+            License::AGPL_3_0 | License::AGPL_3_0Plus => false,
+            _ => true,
+        })
+    }
+
+    /// Whether this can be used under a recognized license without any
+    /// copyleft obligations.
+    pub fn is_permissive(&self) -> bool {
+        self.satisfies(&|license| match *license {
+            License::MIT | License::X11 | License::Unlicense | License::ISC |
+            License::Zlib | License::BSL_1_0 | License::WTFPL | License::BSD_0 |
+            License::BSD_2_Clause | License::BSD_3_Clause | License::Apache_2_0 => true,
+            _ => false,
+        })
+    }
+
     pub fn can_include(&self, other: &License) -> Option<bool> {
         use self::License::*;
 
         if let Unspecified = *other { return Some(false); }
 
+        // Exceptions only grant additional permissions
+        if let WithException(ref license, _) = *self { return license.can_include(other); }
+        if let WithException(ref license, _) = *other { return self.can_include(license); }
+
         if let Custom(_) = *self { return None; }
         if let Custom(_) = *other { return None; }
         if let File(_) = *self { return None; }
         if let File(_) = *other { return None; }
 
-        if let Multiple(ref licenses) = *self {
-            for license in licenses {
-                if let Some(can_include) = license.can_include(other) {
-                    if !can_include {
-                        return Some(false);
+        #[allow(single_match)]
+        match *self {
+            Multiple(ref licenses) | All(ref licenses) => {
+                for license in licenses {
+                    if let Some(can_include) = license.can_include(other) {
+                        if !can_include {
+                            return Some(false);
+                        }
+                    } else {
+                        return None;
                     }
-                } else {
-                    return None;
                 }
+                return Some(true);
             }
-            return Some(true);
+            _ => (),
         }
 
         if let Multiple(ref licenses) = *other {
@@ -87,47 +159,114 @@ impl License {
             return if seen_none { None } else { Some(false) };
         }
 
+        if let All(ref licenses) = *other {
+            let mut seen_none = false;
+            for license in licenses {
+                match self.can_include(license) {
+                    Some(false) => return Some(false),
+                    Some(true) => (),
+                    None => seen_none = true,
+                }
+            }
+            return if seen_none { None } else { Some(true) };
+        }
+
         if let LGPL_2_0 = *self { return None; /* TODO: unknown */ }
         if let LGPL_2_0 = *other { return None; /* TODO: unknown */ }
 
         compatibility!(*self, *other, {
-            Unspecified         => [MIT, X11, BSD_3_Clause]
+            Unspecified         => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause]
 
             LGPL_2_0     => [LGPL_2_0] // TODO: probably allows more
 
-            MIT          => [MIT, X11]
-            X11          => [MIT, X11]
-            BSD_3_Clause => [MIT, X11, BSD_3_Clause]
-            Apache_2_0   => [MIT, X11, BSD_3_Clause, Apache_2_0]
-            MPL_1_1      => [MIT, X11, BSD_3_Clause, MPL_1_1]
-            MPL_2_0      => [MIT, X11, BSD_3_Clause, Apache_2_0, MPL_2_0]
-            LGPL_2_1Plus => [MIT, X11, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus]
-            LGPL_2_1     => [MIT, X11, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus, LGPL_2_1]
-            LGPL_3_0Plus => [MIT, X11, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_3_0Plus]
-            LGPL_3_0     => [MIT, X11, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_3_0Plus, LGPL_3_0]
-            GPL_2_0Plus  => [MIT, X11, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus]
-            GPL_2_0      => [MIT, X11, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_2_0]
-            GPL_3_0Plus  => [MIT, X11, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus]
-            GPL_3_0      => [MIT, X11, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus, GPL_3_0]
-            AGPL_3_0Plus => [MIT, X11, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus, GPL_3_0, AGPL_3_0Plus]
-            AGPL_3_0     => [MIT, X11, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus, GPL_3_0, AGPL_3_0Plus, AGPL_3_0]
+            MIT          => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            X11          => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            Unlicense    => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            ISC          => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            Zlib         => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            BSL_1_0      => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            WTFPL        => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            BSD_0        => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            BSD_2_Clause => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause]
+            BSD_3_Clause => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause]
+            Apache_2_0   => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, Apache_2_0]
+            MPL_1_1      => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_1_1]
+            MPL_2_0      => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, Apache_2_0, MPL_2_0]
+            EUPL_1_2     => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, EUPL_1_2]
+            CDDL_1_0     => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, CDDL_1_0]
+            LGPL_2_1Plus => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus]
+            LGPL_2_1     => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus, LGPL_2_1]
+            LGPL_3_0Plus => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_3_0Plus]
+            LGPL_3_0     => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_3_0Plus, LGPL_3_0]
+            GPL_2_0Plus  => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus]
+            GPL_2_0      => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_2_0]
+            GPL_3_0Plus  => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus]
+            GPL_3_0      => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus, GPL_3_0]
+            AGPL_3_0Plus => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus, GPL_3_0, AGPL_3_0Plus]
+            AGPL_3_0     => [MIT, X11, Unlicense, ISC, Zlib, BSL_1_0, WTFPL, BSD_0, BSD_2_Clause, BSD_3_Clause, MPL_2_0, Apache_2_0, LGPL_2_1Plus, LGPL_2_1, GPL_2_0Plus, GPL_3_0Plus, GPL_3_0, AGPL_3_0Plus, AGPL_3_0]
 
             // TODO: These are `unreachable!()`, can't figure out a nice way to allow this in the macro...
             Custom(_)    => [MIT]
             File(_)      => [MIT]
             Multiple(_)  => [MIT]
+            WithException(_, _) => [MIT]
+            All(_)       => [MIT]
         });
 
-        Some(false)
+        // TODO: Only the inclusion of permissive licenses has been checked for
+        // these, other copyleft licenses may or may not be able to include them
+        match (self, other) {
+            (&EUPL_1_2, _) | (&CDDL_1_0, _) => None,
+            (_, &EUPL_1_2) | (_, &CDDL_1_0) if !self.is_permissive() && *self != Unspecified => None,
+            _ => Some(false),
+        }
     }
 }
 
-impl FromStr for License {
-    type Err = Void;
-    fn from_str(s: &str) -> Result<License, Void> {
-        Ok(match s.trim() {
+impl License {
+    fn any(licenses: Vec<License>) -> License {
+        let mut flattened = Vec::new();
+        for license in licenses {
+            match license {
+                License::Multiple(licenses) => flattened.extend(licenses),
+                license => flattened.push(license),
+            }
+        }
+        flattened.sort();
+        flattened.dedup();
+        if flattened.len() == 1 {
+            return flattened.pop().expect("checked length");
+        }
+        License::Multiple(flattened)
+    }
+
+    fn all(licenses: Vec<License>) -> License {
+        let mut flattened = Vec::new();
+        for license in licenses {
+            match license {
+                License::All(licenses) => flattened.extend(licenses),
+                license => flattened.push(license),
+            }
+        }
+        flattened.sort();
+        flattened.dedup();
+        if flattened.len() == 1 {
+            return flattened.pop().expect("checked length");
+        }
+        License::All(flattened)
+    }
+
+    fn single(s: &str) -> License {
+        match s {
             "MIT"                => License::MIT,
             "X11"                => License::X11,
+            "Unlicense"          => License::Unlicense,
+            "ISC"                => License::ISC,
+            "Zlib"               => License::Zlib,
+            "BSL-1.0"            => License::BSL_1_0,
+            "WTFPL"              => License::WTFPL,
+            "0BSD"               => License::BSD_0,
+            "BSD-2-Clause"       => License::BSD_2_Clause,
             "BSD-3-Clause"       => License::BSD_3_Clause,
             "Apache-2.0"         => License::Apache_2_0,
             "LGPL-2.0"           => License::LGPL_2_0,
@@ -137,22 +276,140 @@ impl FromStr for License {
             "LGPL-3.0+"          => License::LGPL_3_0Plus,
             "MPL-1.1"            => License::MPL_1_1,
             "MPL-2.0"            => License::MPL_2_0,
+            "EUPL-1.2"           => License::EUPL_1_2,
+            "CDDL-1.0"           => License::CDDL_1_0,
             "GPL-2.0"            => License::GPL_2_0,
             "GPL-2.0+"           => License::GPL_2_0Plus,
             "GPL-3.0"            => License::GPL_3_0,
             "GPL-3.0+"           => License::GPL_3_0Plus,
             "AGPL-3.0"           => License::AGPL_3_0,
             "AGPL-3.0+"          => License::AGPL_3_0Plus,
-            s if s.contains('/') => {
-                let mut licenses = s.split('/')
-                    .map(str::parse)
-                    .map(Result::unwrap)
-                    .collect::<Vec<License>>();
-                licenses.sort();
-                License::Multiple(licenses)
-            },
             s => License::Custom(s.to_owned()),
-        })
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+enum Token<'a> {
+    Id(&'a str),
+    And,
+    Or,
+    With,
+    Open,
+    Close,
+}
+
+fn tokenize(s: &str) -> Vec<Token> {
+    fn word(s: &str) -> Token {
+        match s {
+            "OR" => Token::Or,
+            "AND" => Token::And,
+            "WITH" => Token::With,
+            s => Token::Id(s),
+        }
+    }
+
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in s.char_indices() {
+        let token = match c {
+            '(' => Some(Token::Open),
+            ')' => Some(Token::Close),
+            '/' => Some(Token::Or),
+            c if c.is_whitespace() => None,
+            _ => {
+                start = start.or(Some(i));
+                continue;
+            }
+        };
+        if let Some(start) = start.take() {
+            tokens.push(word(&s[start..i]));
+        }
+        tokens.extend(token);
+    }
+    if let Some(start) = start {
+        tokens.push(word(&s[start..]));
+    }
+    tokens
+}
+
+// Parses SPDX style license expressions, `WITH` binds tighter than `AND` which
+// binds tighter than `OR`, the legacy `/` separator is treated as `OR`.
+struct Parser<'a> {
+    tokens: Peekable<vec::IntoIter<Token<'a>>>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(s: &'a str) -> Result<License, ()> {
+        let mut parser = Parser { tokens: tokenize(s).into_iter().peekable() };
+        let license = parser.or()?;
+        match parser.tokens.next() {
+            None => Ok(license),
+            Some(_) => Err(()),
+        }
+    }
+
+    fn or(&mut self) -> Result<License, ()> {
+        let mut licenses = vec![self.and()?];
+        while self.tokens.peek() == Some(&Token::Or) {
+            self.tokens.next();
+            licenses.push(self.and()?);
+        }
+        Ok(License::any(licenses))
+    }
+
+    fn and(&mut self) -> Result<License, ()> {
+        let mut licenses = vec![self.with()?];
+        while self.tokens.peek() == Some(&Token::And) {
+            self.tokens.next();
+            licenses.push(self.with()?);
+        }
+        Ok(License::all(licenses))
+    }
+
+    fn with(&mut self) -> Result<License, ()> {
+        let license = self.atom()?;
+        if self.tokens.peek() != Some(&Token::With) {
+            return Ok(license);
+        }
+        self.tokens.next();
+        match self.tokens.next() {
+            Some(Token::Id(exception)) => Ok(License::WithException(Box::new(license), exception.to_owned())),
+            _ => Err(()),
+        }
+    }
+
+    fn atom(&mut self) -> Result<License, ()> {
+        match self.tokens.next() {
+            Some(Token::Open) => {
+                let license = self.or()?;
+                match self.tokens.next() {
+                    Some(Token::Close) => Ok(license),
+                    _ => Err(()),
+                }
+            }
+            Some(Token::Id(id)) => {
+                // Unknown licenses may be free text containing spaces
+                let mut words = vec![id];
+                loop {
+                    let id = match self.tokens.peek() {
+                        Some(&Token::Id(id)) => id,
+                        _ => break,
+                    };
+                    words.push(id);
+                    self.tokens.next();
+                }
+                Ok(License::single(&words.join(" ")))
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromStr for License {
+    type Err = Void;
+    fn from_str(s: &str) -> Result<License, Void> {
+        Ok(Parser::parse(s).unwrap_or_else(|()| License::Custom(s.trim().to_owned())))
     }
 }
 
@@ -161,6 +418,13 @@ impl fmt::Display for License {
         match *self {
             License::MIT           => write!(w, "MIT"),
             License::X11           => write!(w, "X11"),
+            License::Unlicense     => write!(w, "Unlicense"),
+            License::ISC           => write!(w, "ISC"),
+            License::Zlib          => write!(w, "Zlib"),
+            License::BSL_1_0       => write!(w, "BSL-1.0"),
+            License::WTFPL         => write!(w, "WTFPL"),
+            License::BSD_0         => write!(w, "0BSD"),
+            License::BSD_2_Clause  => write!(w, "BSD-2-Clause"),
             License::BSD_3_Clause  => write!(w, "BSD-3-Clause"),
             License::Apache_2_0    => write!(w, "Apache-2.0"),
             License::LGPL_2_0      => write!(w, "LGPL-2.0"),
@@ -170,6 +434,8 @@ impl fmt::Display for License {
             License::LGPL_3_0Plus  => write!(w, "LGPL-3.0+"),
             License::MPL_1_1       => write!(w, "MPL-1.1"),
             License::MPL_2_0       => write!(w, "MPL-2.0"),
+            License::EUPL_1_2      => write!(w, "EUPL-1.2"),
+            License::CDDL_1_0      => write!(w, "CDDL-1.0"),
             License::GPL_2_0       => write!(w, "GPL-2.0"),
             License::GPL_2_0Plus   => write!(w, "GPL-2.0+"),
             License::GPL_3_0       => write!(w, "GPL-3.0"),
@@ -185,8 +451,189 @@ impl fmt::Display for License {
                 }
                 write!(w, ")")
             },
+            License::All(ref ls)   => {
+                write!(w, "All({}", ls[0])?;
+                for l in ls.iter().skip(1) {
+                    write!(w, ", {}", l)?;
+                }
+                write!(w, ")")
+            },
+            License::WithException(ref l, ref e) => write!(w, "{} WITH {}", l, e),
             License::Unspecified          => write!(w, "Unlicensed"),
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use void::ResultVoidExt;
+
+    use super::License;
+    use super::License::*;
+
+    fn parse(s: &str) -> License {
+        s.parse::<License>().void_unwrap()
+    }
+
+    #[test]
+    fn single() {
+        assert_eq!(parse("MIT"), MIT);
+        assert_eq!(parse(" Apache-2.0 "), Apache_2_0);
+        assert_eq!(parse("GPL-3.0+"), GPL_3_0Plus);
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            parse("MIT OR Apache-2.0 AND BSD-3-Clause"),
+            Multiple(vec![MIT, All(vec![BSD_3_Clause, Apache_2_0])]));
+        assert_eq!(
+            parse("MIT AND Apache-2.0 OR BSD-3-Clause"),
+            Multiple(vec![BSD_3_Clause, All(vec![MIT, Apache_2_0])]));
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(
+            parse("(MIT OR Apache-2.0) AND BSD-3-Clause"),
+            All(vec![BSD_3_Clause, Multiple(vec![MIT, Apache_2_0])]));
+        assert_eq!(parse("(MIT)"), MIT);
+    }
+
+    #[test]
+    fn slash_is_or() {
+        assert_eq!(parse("MIT/Apache-2.0"), Multiple(vec![MIT, Apache_2_0]));
+        assert_eq!(parse("MIT / Apache-2.0"), parse("MIT OR Apache-2.0"));
+    }
+
+    #[test]
+    fn nested_groups_flatten() {
+        assert_eq!(parse("MIT OR (ISC OR Apache-2.0)"), Multiple(vec![MIT, ISC, Apache_2_0]));
+        assert_eq!(parse("(MIT AND ISC) AND Zlib"), All(vec![MIT, ISC, Zlib]));
+    }
+
+    #[test]
+    fn operands_sort_and_dedup() {
+        assert_eq!(parse("Apache-2.0 OR MIT"), parse("MIT OR Apache-2.0"));
+        assert_eq!(parse("Apache-2.0 AND MIT"), parse("MIT AND Apache-2.0"));
+        assert_eq!(parse("Apache-2.0 OR MIT OR MIT"), Multiple(vec![MIT, Apache_2_0]));
+        assert_eq!(parse("MIT AND MIT"), MIT);
+        assert_eq!(parse("MIT/Apache-2.0"), parse("Apache-2.0 OR MIT"));
+    }
+
+    #[test]
+    fn is_permissive() {
+        assert!(MIT.is_permissive());
+        assert!(parse("Apache-2.0 WITH LLVM-exception").is_permissive());
+        assert!(parse("GPL-3.0 OR MIT").is_permissive());
+        for s in &["LGPL-2.1", "MPL-2.0", "EUPL-1.2", "CDDL-1.0", "GPL-3.0", "MIT AND GPL-3.0", "Foo"] {
+            assert!(!parse(s).is_permissive(), "{}", s);
+        }
+        assert!(!Unspecified.is_permissive());
+    }
+
+    #[test]
+    fn is_strong_copyleft() {
+        assert!(GPL_2_0.is_strong_copyleft());
+        assert!(parse("AGPL-3.0 AND MIT").is_strong_copyleft());
+        assert!(!parse("GPL-3.0 OR MIT").is_strong_copyleft());
+        assert!(!LGPL_3_0.is_strong_copyleft());
+    }
+
+    #[test]
+    fn with_exception() {
+        let llvm = WithException(Box::new(Apache_2_0), "LLVM-exception".to_owned());
+        assert_eq!(parse("Apache-2.0 WITH LLVM-exception"), llvm);
+        assert_eq!(parse("MIT OR Apache-2.0 WITH LLVM-exception"), Multiple(vec![MIT, llvm.clone()]));
+        assert_eq!(llvm.to_string(), "Apache-2.0 WITH LLVM-exception");
+        assert_eq!(parse("Apache-2.0 WITH"), Custom("Apache-2.0 WITH".to_owned()));
+    }
+
+    #[test]
+    fn can_include_with_exception() {
+        let llvm = parse("Apache-2.0 WITH LLVM-exception");
+        assert_eq!(llvm.can_include(&MIT), Some(true));
+        assert_eq!(llvm.can_include(&GPL_3_0), Some(false));
+        assert_eq!(Apache_2_0.can_include(&llvm), Some(true));
+        assert_eq!(MIT.can_include(&llvm), Some(false));
+        assert!(llvm.satisfies(&|l| *l == Apache_2_0));
+    }
+
+    #[test]
+    fn unparseable_is_custom() {
+        for s in &["MIT OR", "MIT (with extras)", "(MIT", "MIT/", ""] {
+            assert_eq!(parse(s), Custom(s.to_string()));
+        }
+    }
+
+    #[test]
+    fn free_text_is_custom() {
+        for s in &["see LICENSE and NOTICE", "Proprietary or custom", "Licensed with permission"] {
+            let license = parse(s);
+            assert_eq!(license, Custom(s.to_string()));
+            assert!(license.satisfies(&|l| *l == Custom(s.to_string())));
+        }
+        assert_eq!(parse("Foo Bar/MIT"), Multiple(vec![MIT, Custom("Foo Bar".to_owned())]));
+    }
+
+    #[test]
+    fn satisfies() {
+        let license = parse("MIT OR (Apache-2.0 AND GPL-3.0)");
+        assert!(license.satisfies(&|l| *l == MIT));
+        assert!(license.satisfies(&|l| *l != MIT));
+        assert!(!license.satisfies(&|l| *l == Apache_2_0));
+        assert!(!parse("MIT AND Apache-2.0").satisfies(&|l| *l == MIT));
+    }
+
+    #[test]
+    fn or_later_counterpart() {
+        assert_eq!(GPL_3_0.or_later_counterpart(), Some(GPL_3_0Plus));
+        assert_eq!(GPL_3_0Plus.or_later_counterpart(), Some(GPL_3_0));
+        assert_eq!(LGPL_2_1.or_later_counterpart(), Some(LGPL_2_1Plus));
+        assert_eq!(AGPL_3_0Plus.or_later_counterpart(), Some(AGPL_3_0));
+        assert_eq!(MIT.or_later_counterpart(), None);
+    }
+
+    #[test]
+    fn bsd_2_clause_is_in_the_mit_tier() {
+        for license in &[MIT, ISC, Zlib, BSD_2_Clause] {
+            assert_eq!(license.can_include(&BSD_2_Clause), Some(true));
+            assert_eq!(BSD_2_Clause.can_include(license), Some(true));
+        }
+        assert_eq!(MIT.can_include(&BSD_3_Clause), Some(false));
+    }
+
+    #[test]
+    fn can_include_eupl_cddl() {
+        for license in &[EUPL_1_2, CDDL_1_0] {
+            assert_eq!(license.can_include(&MIT), Some(true));
+            assert_eq!(license.can_include(license), Some(true));
+            assert_eq!(license.can_include(&GPL_3_0), None);
+            assert_eq!(MIT.can_include(license), Some(false));
+            assert_eq!(Apache_2_0.can_include(license), Some(false));
+            assert_eq!(BSD_3_Clause.can_include(license), Some(false));
+            assert_eq!(Unspecified.can_include(license), Some(false));
+            assert_eq!(GPL_3_0.can_include(license), None);
+            assert_eq!(MPL_2_0.can_include(license), None);
+        }
+        assert_eq!(EUPL_1_2.can_include(&CDDL_1_0), None);
+    }
+
+    #[test]
+    fn can_include_any() {
+        assert_eq!(MIT.can_include(&parse("MIT OR GPL-3.0")), Some(true));
+        assert_eq!(MIT.can_include(&parse("GPL-2.0 OR GPL-3.0")), Some(false));
+        assert_eq!(parse("MIT OR Apache-2.0").can_include(&Apache_2_0), Some(false));
+        assert_eq!(parse("MIT OR Apache-2.0").can_include(&MIT), Some(true));
+    }
+
+    #[test]
+    fn can_include_all() {
+        assert_eq!(Apache_2_0.can_include(&parse("MIT AND Apache-2.0")), Some(true));
+        assert_eq!(MIT.can_include(&parse("MIT AND Apache-2.0")), Some(false));
+        assert_eq!(MIT.can_include(&parse("MIT AND Foo")), None);
+        assert_eq!(parse("MIT AND Apache-2.0").can_include(&MIT), Some(true));
+        assert_eq!(parse("MIT AND Apache-2.0").can_include(&Apache_2_0), Some(false));
+    }
+}
@@ -1,20 +1,33 @@
 use cargo::core::Package;
 use license::License;
 
+#[derive(Copy, Clone, Debug)]
+pub struct DeclaredLicense<'a> {
+    pub license: Option<&'a str>,
+    pub license_file: Option<&'a str>,
+}
+
 pub trait Licensed {
     fn license(&self) -> License;
+    fn declared_license(&self) -> DeclaredLicense;
 }
 
 impl Licensed for Package {
     fn license(&self) -> License {
-        let metadata = self.manifest().metadata();
-        metadata.license
-            .as_ref()
+        let declared = self.declared_license();
+        declared.license
             .and_then(|license| license.parse::<License>().ok())
-            .or_else(|| metadata.license_file
-                     .as_ref()
+            .or_else(|| declared.license_file
                      .and_then(|file| self.root().join(file).canonicalize().ok())
                      .map(License::File))
             .unwrap_or_default()
     }
+
+    fn declared_license(&self) -> DeclaredLicense {
+        let metadata = self.manifest().metadata();
+        DeclaredLicense {
+            license: metadata.license.as_ref().map(String::as_str),
+            license_file: metadata.license_file.as_ref().map(String::as_str),
+        }
+    }
 }
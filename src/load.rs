@@ -1,21 +1,26 @@
 use std::collections::HashSet;
 
 use cargo::core::dependency::Kind;
-use cargo::core::{ Package, Workspace };
+use cargo::core::{ Package, PackageIdSpec, Workspace };
 use cargo::ops;
 use cargo::util::important_paths::find_root_manifest_for_wd;
 use cargo::{ Config, CargoResult };
 
 pub fn resolve_packages(
         manifest_path: Option<String>,
+        package: Option<String>,
         config: &Config) -> CargoResult<(Package, Vec<Package>)> {
     let root = find_root_manifest_for_wd(manifest_path, config.cwd())?;
     let workspace = Workspace::new(&root, config)?;
-    let current = workspace.current()?;
     let (packages, resolve) = ops::resolve_ws(&workspace)?;
+    let current_id = match package {
+        Some(spec) => PackageIdSpec::parse(&spec)?.query(resolve.iter())?.clone(),
+        None => workspace.current()?.package_id().clone(),
+    };
+    let current = packages.get(&current_id)?;
 
     let mut result = HashSet::new();
-    let mut to_check = vec![current.package_id()];
+    let mut to_check = vec![&current_id];
     while let Some(id) = to_check.pop() {
         if let Ok(package) = packages.get(id) {
             if result.insert(package) {
@@ -33,5 +38,27 @@ pub fn resolve_packages(
         }
     }
 
-    Ok((current.clone(), result.into_iter().cloned().collect()))
+    let mut result = result.into_iter().cloned().collect::<Vec<Package>>();
+    result.sort_by(|a, b| a.package_id().cmp(b.package_id()));
+
+    Ok((current.clone(), result))
+}
+
+pub fn exclude_packages(packages: Vec<Package>, exclude: &[String], config: &Config) -> CargoResult<Vec<Package>> {
+    let matches = |spec: &String, package: &Package| {
+        let mut parts = spec.splitn(2, '@');
+        let name = parts.next().expect("splitn always yields one part");
+        name == package.name() && parts.next()
+            .map_or(true, |version| version == package.version().to_string())
+    };
+
+    for spec in exclude {
+        if !packages.iter().any(|package| matches(spec, package)) {
+            config.shell().warn(format!("Package {} passed to --exclude did not match any package", spec))?;
+        }
+    }
+
+    Ok(packages.into_iter()
+        .filter(|package| !exclude.iter().any(|spec| matches(spec, package)))
+        .collect())
 }
@@ -1,17 +1,17 @@
 #[macro_use] extern crate clap;
 extern crate cargo;
+extern crate cargo_lichking;
 extern crate void;
 
-mod license;
-mod licensed;
-mod load;
 mod check;
 mod list;
 mod options;
 
+use std::cmp;
 use std::process;
 
-use cargo::{ Config, CliResult };
+use cargo::{ Config, CliError };
+use cargo_lichking::{ license, licensed, load };
 
 use options::{ Options, Cmd };
 
@@ -19,14 +19,18 @@ fn main() {
     let matches = Options::app(false).get_matches();
     let options = Options::from_matches(&matches);
     let config = Config::default().expect("No idea why this would fail");
-    let result = real_main(options, &config);
-    if let Err(err) = result {
-        config.shell().error(err).expect("Can't do much");
-        process::exit(1);
+    match real_main(options, &config) {
+        Ok(0) => (),
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            config.shell().error(err).expect("Can't do much");
+            process::exit(1);
+        }
     }
 }
 
-fn real_main(options: Options, config: &Config) -> CliResult {
+/// Returns the exit code to use when successful
+fn real_main(options: Options, config: &Config) -> Result<i32, CliError> {
     config.configure(
         options.verbose,
         Some(options.quiet),
@@ -36,14 +40,17 @@ fn real_main(options: Options, config: &Config) -> CliResult {
 
     config.shell().warn("IANAL: This is not legal advice and is not guaranteed to be correct.")?;
 
-    let (root, packages) = load::resolve_packages(options.manifest_path, config)?;
+    let (root, packages) = load::resolve_packages(options.manifest_path, options.package, config)?;
+    let packages = load::exclude_packages(packages, &options.exclude, config)?;
 
     match options.cmd {
-        Cmd::Check => check::run(&root, packages, config)?,
+        Cmd::Check(options) => {
+            let fail = check::run(&root, packages, config, &options)?;
+            Ok(cmp::min(fail, 255) as i32)
+        }
         Cmd::List { by } => {
-            list::run(packages, config, by)?
+            list::run(packages, config, by)?;
+            Ok(0)
         }
     }
-
-    Ok(())
 }
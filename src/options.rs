@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
 use clap::{ App, Arg, SubCommand, AppSettings, ArgMatches };
+use void::ResultVoidExt;
+
+use license::License;
 
 #[derive(Copy, Clone)]
 pub enum By {
@@ -8,12 +11,28 @@ pub enum By {
     Crate,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Policy {
+    Proprietary,
+    CopyleftOk,
+}
+
+#[derive(Clone)]
+pub struct CheckOptions {
+    pub deny: Vec<License>,
+    pub allow: Vec<License>,
+    pub allow_unspecified: bool,
+    pub no_unknown: bool,
+    pub policy: Policy,
+    pub count: bool,
+}
+
+#[derive(Clone)]
 pub enum Cmd {
     List {
         by: By
     },
-    Check,
+    Check(CheckOptions),
 }
 
 #[derive(Clone)]
@@ -21,6 +40,8 @@ pub struct Options {
     pub verbose: u32,
     pub quiet: bool,
     pub manifest_path: Option<String>,
+    pub package: Option<String>,
+    pub exclude: Vec<String>,
     pub color: Option<String>,
     pub frozen: bool,
     pub locked: bool,
@@ -75,6 +96,15 @@ impl Options {
                 .long("manifest-path")
                 .takes_value(true).value_name("PATH")
                 .help("Path to the manifest to analyze"),
+            Arg::with_name("package")
+                .short("p").long("package")
+                .takes_value(true).value_name("SPEC")
+                .help("Package to analyze the dependencies of"),
+            Arg::with_name("exclude")
+                .long("exclude")
+                .takes_value(true).value_name("PACKAGES")
+                .use_delimiter(true)
+                .help("Comma separated packages (NAME or NAME@VERSION) to ignore"),
             Arg::with_name("color")
                 .long("color")
                 .takes_value(true).value_name("COLOR")
@@ -92,7 +122,34 @@ impl Options {
     pub fn subcommands() -> Vec<App<'static, 'static>> {
         vec![
             SubCommand::with_name("check")
-                .about("Check that all dependencies have a compatible license with this crate"),
+                .about("Check that all dependencies have a compatible license with this crate")
+                .args(&[
+                    Arg::with_name("deny")
+                        .long("deny")
+                        .takes_value(true).value_name("LICENSES")
+                        .use_delimiter(true)
+                        .help("Comma separated licenses that no dependency may be used under"),
+                    Arg::with_name("allow")
+                        .long("allow")
+                        .takes_value(true).value_name("LICENSES")
+                        .use_delimiter(true)
+                        .help("Comma separated licenses that every dependency must be usable under"),
+                    Arg::with_name("allow-unspecified")
+                        .long("allow-unspecified")
+                        .help("Only warn about dependencies that do not specify a license"),
+                    Arg::with_name("no-unknown")
+                        .long("no-unknown")
+                        .help("Fail on dependencies whose license is not recognized"),
+                    Arg::with_name("license-policy")
+                        .long("license-policy")
+                        .takes_value(true).value_name("POLICY")
+                        .possible_values(&["proprietary", "copyleft-ok"])
+                        .default_value("proprietary")
+                        .help("Whether to warn about strong copyleft dependencies mixed with permissive ones"),
+                    Arg::with_name("count")
+                        .long("count")
+                        .help("Only print the number of failing or unknown dependencies and exit with it"),
+                ]),
             SubCommand::with_name("list")
                 .about("List licensing of all dependencies")
                 .args(&[
@@ -112,11 +169,27 @@ impl Options {
             verbose: matches.occurrences_of("verbose") as u32,
             quiet: matches.is_present("quiet"),
             manifest_path: matches.value_of("manifest-path").map(ToOwned::to_owned),
+            package: matches.value_of("package").map(ToOwned::to_owned),
+            exclude: matches.values_of("exclude")
+                .map(|values| values.map(ToOwned::to_owned).collect())
+                .unwrap_or_else(Vec::new),
             color: matches.value_of("color").map(ToOwned::to_owned),
             frozen: matches.is_present("frozen"),
             locked: matches.is_present("locked"),
             cmd: match matches.subcommand() {
-                ("check", Some(_)) => Cmd::Check,
+                ("check", Some(matches)) => {
+                    Cmd::Check(CheckOptions {
+                        deny: Options::licenses(matches, "deny"),
+                        allow: Options::licenses(matches, "allow"),
+                        allow_unspecified: matches.is_present("allow-unspecified"),
+                        no_unknown: matches.is_present("no-unknown"),
+                        policy: matches.value_of("license-policy")
+                            .expect("defaulted")
+                            .parse()
+                            .expect("constrained"),
+                        count: matches.is_present("count"),
+                    })
+                }
                 ("list", Some(matches)) => {
                     Cmd::List {
                         by: matches.value_of("by")
@@ -132,6 +205,12 @@ impl Options {
             },
         }
     }
+
+    fn licenses(matches: &ArgMatches, name: &str) -> Vec<License> {
+        matches.values_of(name)
+            .map(|values| values.map(|value| value.parse().void_unwrap()).collect())
+            .unwrap_or_else(Vec::new)
+    }
 }
 
 impl FromStr for By {
@@ -144,3 +223,14 @@ impl FromStr for By {
         }
     }
 }
+
+impl FromStr for Policy {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "proprietary" => Ok(Policy::Proprietary),
+            "copyleft-ok" => Ok(Policy::CopyleftOk),
+            s => Err(format!("Cannot parse Policy from '{}'", s)),
+        }
+    }
+}